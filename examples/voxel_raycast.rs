@@ -2,6 +2,7 @@ use ::glam::Vec3;
 use macroquad::{prelude::*, rand::rand};
 
 use paraxis::voxels::SparseVoxelOctree; // replace with your crate path
+use paraxis::voxels::shading::{self, Light};
 
 #[macroquad::main("Octree Raycast Test")]
 async fn main() {
@@ -23,7 +24,12 @@ async fn main() {
 	}
 
     let camera_position = Vec3::new(16.0, 8.0, -16.0);
-	
+
+    let lights = [Light::Directional {
+        direction: Vec3::new(-0.4, -1.0, 0.3).normalize(),
+        color: Vec3::new(1.0, 1.0, 0.95),
+    }];
+
     loop {
         let mut rgba_data = vec![0u8; (screen_width * screen_height * 4) as usize];
         for i in 0..screen_width {
@@ -35,10 +41,16 @@ async fn main() {
 
 				let ray_direction = Vec3::new(ndc_x, ndc_y, 1.0).normalize();
 
-                let color = if let Some((_voxel, _hit_position)) =
-                    octree.raycast(ray_origin, ray_direction)
-                {
-                    (255, 0, 0, 255)
+                let color = if let Some(hit) = octree.raycast(ray_origin, ray_direction) {
+                    let lit = shading::shade(&octree, hit.position, hit.normal, &lights);
+                    let ao = shading::ambient_occlusion(&octree, hit.position, hit.normal);
+                    let shaded = lit * ao;
+                    (
+                        (shaded.x.clamp(0.0, 1.0) * 255.0) as u8,
+                        0,
+                        0,
+                        255,
+                    )
                 } else {
                     (0, 0, 0, 255)
                 };
@@ -1,4 +1,4 @@
-pub mod voxel;
+pub mod voxels;
 
 #[derive(Clone, Debug)]
 pub struct KDNode<const K: usize> {
@@ -10,6 +10,243 @@ pub struct KDNode<const K: usize> {
 #[derive(Clone, Debug)]
 pub struct KDTree<const K: usize> {
     kd_nodes: Vec<KDNode<K>>,
+    sah_nodes: Vec<SahNode>,
+    sah_bounds: Option<([f32; 3], [f32; 3])>,
+}
+
+/// A bounded primitive fed into [`KDTree::build_sah`]: the index of the
+/// primitive in the caller's own array, plus its world-space AABB.
+#[derive(Clone, Copy, Debug)]
+pub struct BoundedPrimitive {
+    pub index: usize,
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+/// A node of an SAH-built [`KDTree`] accelerator: an interior split plane
+/// with child links, or a leaf holding the primitive indices it bounds.
+#[derive(Clone, Debug)]
+struct SahNode {
+    axis: usize,
+    split: f32,
+    left: Option<usize>,
+    right: Option<usize>,
+    primitives: Vec<usize>,
+}
+
+const SAH_TRAVERSAL_COST: f32 = 1.0;
+const SAH_INTERSECT_COST: f32 = 1.0;
+
+fn aabb_of(primitives: &[BoundedPrimitive]) -> ([f32; 3], [f32; 3]) {
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for p in primitives {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(p.min[axis]);
+            max[axis] = max[axis].max(p.max[axis]);
+        }
+    }
+    (min, max)
+}
+
+fn surface_area(min: [f32; 3], max: [f32; 3]) -> f32 {
+    let d = [max[0] - min[0], max[1] - min[1], max[2] - min[2]];
+    2.0 * (d[0] * d[1] + d[1] * d[2] + d[2] * d[0])
+}
+
+fn build_sah_node(primitives: Vec<BoundedPrimitive>, nodes: &mut Vec<SahNode>) -> Option<usize> {
+    if primitives.is_empty() {
+        return None;
+    }
+
+    let (node_min, node_max) = aabb_of(&primitives);
+    let node_area = surface_area(node_min, node_max);
+    let leaf_cost = SAH_INTERSECT_COST * primitives.len() as f32;
+
+    let mut best: Option<(usize, f32, f32)> = None;
+    for axis in 0..3 {
+        let mut candidates: Vec<f32> = primitives
+            .iter()
+            .flat_map(|p| [p.min[axis], p.max[axis]])
+            .collect();
+        candidates.sort_by(f32::total_cmp);
+        candidates.dedup();
+
+        for &split in &candidates {
+            let n_left = primitives.iter().filter(|p| p.min[axis] <= split).count();
+            let n_right = primitives.iter().filter(|p| p.max[axis] >= split).count();
+            // A split that doesn't shrink either side (e.g. every primitive
+            // straddles it) makes no progress and recurses forever, so
+            // require each side to be strictly smaller than the parent.
+            if n_left == 0 || n_right == 0 || n_left >= primitives.len() || n_right >= primitives.len() {
+                continue;
+            }
+
+            let mut left_max = node_max;
+            left_max[axis] = split;
+            let mut right_min = node_min;
+            right_min[axis] = split;
+
+            let cost = SAH_TRAVERSAL_COST
+                + SAH_INTERSECT_COST
+                    * (surface_area(node_min, left_max) / node_area * n_left as f32
+                        + surface_area(right_min, node_max) / node_area * n_right as f32);
+
+            if best.is_none_or(|(_, _, best_cost)| cost < best_cost) {
+                best = Some((axis, split, cost));
+            }
+        }
+    }
+
+    let Some((axis, split, _cost)) = best.filter(|&(_, _, cost)| cost < leaf_cost) else {
+        let current_index = nodes.len();
+        nodes.push(SahNode {
+            axis: 0,
+            split: 0.0,
+            left: None,
+            right: None,
+            primitives: primitives.into_iter().map(|p| p.index).collect(),
+        });
+        return Some(current_index);
+    };
+
+    let mut left_primitives = Vec::new();
+    let mut right_primitives = Vec::new();
+    for p in &primitives {
+        if p.min[axis] <= split {
+            left_primitives.push(*p);
+        }
+        if p.max[axis] >= split {
+            right_primitives.push(*p);
+        }
+    }
+
+    let current_index = nodes.len();
+    nodes.push(SahNode {
+        axis,
+        split,
+        left: None,
+        right: None,
+        primitives: Vec::new(),
+    });
+    let left = build_sah_node(left_primitives, nodes);
+    let right = build_sah_node(right_primitives, nodes);
+    nodes[current_index].left = left;
+    nodes[current_index].right = right;
+
+    Some(current_index)
+}
+
+impl KDTree<3> {
+    /// Builds a ray-tracing accelerator over bounded primitives using the
+    /// Surface Area Heuristic, instead of the median point split that
+    /// [`KDTree::generate`] uses. At each node, candidate split planes are
+    /// taken from primitive bounds on each axis and scored by
+    /// `C_trav + C_isect * (A_left/A_node * N_left + A_right/A_node * N_right)`;
+    /// the cheapest candidate is kept only if it beats leaving the node as a
+    /// leaf. Primitives that straddle a split land in both children.
+    pub fn build_sah(primitives: Vec<BoundedPrimitive>) -> Self {
+        let sah_bounds = (!primitives.is_empty()).then(|| aabb_of(&primitives));
+        let mut sah_nodes = Vec::new();
+        build_sah_node(primitives, &mut sah_nodes);
+        Self {
+            kd_nodes: Vec::new(),
+            sah_nodes,
+            sah_bounds,
+        }
+    }
+
+    /// Walks the SAH tree along `ray_origin + t * ray_direction` and returns
+    /// the indices of every primitive in a leaf the ray passes through, in
+    /// the order their leaves are entered. Leaves outside the ray's path
+    /// through the tree are never visited, so this is the traversal that
+    /// makes [`build_sah`](Self::build_sah)'s split planes actually skip
+    /// empty space rather than just partitioning primitives on paper.
+    pub fn raycast_sah(&self, ray_origin: [f32; 3], ray_direction: [f32; 3]) -> Vec<usize> {
+        let mut hits = Vec::new();
+        let Some((bounds_min, bounds_max)) = self.sah_bounds else {
+            return hits;
+        };
+        let Some((t_min, t_max)) = ray_aabb_t_range(ray_origin, ray_direction, bounds_min, bounds_max) else {
+            return hits;
+        };
+
+        fn walk(
+            nodes: &[SahNode],
+            node_index: Option<usize>,
+            origin: [f32; 3],
+            direction: [f32; 3],
+            t_min: f32,
+            t_max: f32,
+            hits: &mut Vec<usize>,
+        ) {
+            let Some(index) = node_index else {
+                return;
+            };
+            let node = &nodes[index];
+
+            if node.left.is_none() && node.right.is_none() {
+                hits.extend_from_slice(&node.primitives);
+                return;
+            }
+
+            let axis = node.axis;
+            if direction[axis].abs() < f32::EPSILON {
+                let near = if origin[axis] <= node.split { node.left } else { node.right };
+                walk(nodes, near, origin, direction, t_min, t_max, hits);
+                return;
+            }
+
+            let t_split = (node.split - origin[axis]) / direction[axis];
+            let (near, far) = if origin[axis] <= node.split {
+                (node.left, node.right)
+            } else {
+                (node.right, node.left)
+            };
+
+            if t_split > t_max || t_split < 0.0 {
+                walk(nodes, near, origin, direction, t_min, t_max, hits);
+            } else if t_split < t_min {
+                walk(nodes, far, origin, direction, t_min, t_max, hits);
+            } else {
+                walk(nodes, near, origin, direction, t_min, t_split, hits);
+                walk(nodes, far, origin, direction, t_split, t_max, hits);
+            }
+        }
+
+        walk(&self.sah_nodes, Some(0), ray_origin, ray_direction, t_min.max(0.0), t_max, &mut hits);
+        hits
+    }
+}
+
+fn ray_aabb_t_range(
+    origin: [f32; 3],
+    direction: [f32; 3],
+    min: [f32; 3],
+    max: [f32; 3],
+) -> Option<(f32, f32)> {
+    let mut t_min = f32::NEG_INFINITY;
+    let mut t_max = f32::INFINITY;
+
+    for axis in 0..3 {
+        if direction[axis].abs() < f32::EPSILON {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+
+        let inv_dir = 1.0 / direction[axis];
+        let mut t0 = (min[axis] - origin[axis]) * inv_dir;
+        let mut t1 = (max[axis] - origin[axis]) * inv_dir;
+        if t0 > t1 {
+            std::mem::swap(&mut t0, &mut t1);
+        }
+        t_min = t_min.max(t0);
+        t_max = t_max.min(t1);
+    }
+
+    (t_max >= t_min).then_some((t_min, t_max))
 }
 
 impl<const K: usize> KDTree<K> {
@@ -50,8 +287,100 @@ impl<const K: usize> KDTree<K> {
         let mut points_copy = points.clone();
         traverse_points(&mut points_copy, 0, &mut kd_nodes);
 
-        Self { kd_nodes }
+        Self {
+            kd_nodes,
+            sah_nodes: Vec::new(),
+            sah_bounds: None,
+        }
     }
+
+    /// Returns the index of the point nearest `query`, or `None` if the
+    /// tree was built empty. Descends into the side of the splitting plane
+    /// containing the query first, then only backtracks into the far side
+    /// when the current best distance reaches across the plane.
+    pub fn nearest(&self, query: [f32; K]) -> Option<usize> {
+        fn search<const K: usize>(
+            nodes: &[KDNode<K>],
+            node_index: Option<usize>,
+            query: [f32; K],
+            depth: usize,
+            best: &mut Option<(usize, f32)>,
+        ) {
+            let Some(index) = node_index else {
+                return;
+            };
+            let node = &nodes[index];
+
+            let dist = dist_sq(query, node.position);
+            if best.is_none_or(|(_, best_dist)| dist < best_dist) {
+                *best = Some((index, dist));
+            }
+
+            let axis = depth % K;
+            let diff = query[axis] - node.position[axis];
+            let (near, far) = if diff < 0.0 {
+                (node.left, node.right)
+            } else {
+                (node.right, node.left)
+            };
+
+            search(nodes, near, query, depth + 1, best);
+            if best.is_none_or(|(_, best_dist)| diff * diff < best_dist) {
+                search(nodes, far, query, depth + 1, best);
+            }
+        }
+
+        if self.kd_nodes.is_empty() {
+            return None;
+        }
+        let mut best = None;
+        search(&self.kd_nodes, Some(0), query, 0, &mut best);
+        best.map(|(index, _)| index)
+    }
+
+    /// Returns the indices of every point within radius `r` of `query`.
+    pub fn within_radius(&self, query: [f32; K], r: f32) -> Vec<usize> {
+        fn search<const K: usize>(
+            nodes: &[KDNode<K>],
+            node_index: Option<usize>,
+            query: [f32; K],
+            r_sq: f32,
+            depth: usize,
+            results: &mut Vec<usize>,
+        ) {
+            let Some(index) = node_index else {
+                return;
+            };
+            let node = &nodes[index];
+
+            if dist_sq(query, node.position) <= r_sq {
+                results.push(index);
+            }
+
+            let axis = depth % K;
+            let diff = query[axis] - node.position[axis];
+            let (near, far) = if diff < 0.0 {
+                (node.left, node.right)
+            } else {
+                (node.right, node.left)
+            };
+
+            search(nodes, near, query, r_sq, depth + 1, results);
+            if diff * diff < r_sq {
+                search(nodes, far, query, r_sq, depth + 1, results);
+            }
+        }
+
+        let mut results = Vec::new();
+        if !self.kd_nodes.is_empty() {
+            search(&self.kd_nodes, Some(0), query, r * r, 0, &mut results);
+        }
+        results
+    }
+}
+
+fn dist_sq<const K: usize>(a: [f32; K], b: [f32; K]) -> f32 {
+    (0..K).map(|i| (a[i] - b[i]).powi(2)).sum()
 }
 
 #[cfg(test)]
@@ -89,4 +418,141 @@ mod tests {
             );
         }
     }
+
+    fn unit_box_at(index: usize, min: [f32; 3]) -> BoundedPrimitive {
+        BoundedPrimitive {
+            index,
+            min,
+            max: [min[0] + 1.0, min[1] + 1.0, min[2] + 1.0],
+        }
+    }
+
+    #[test]
+    fn build_sah_keeps_a_single_leaf_when_splitting_does_not_pay_for_itself() {
+        // Two tiny, overlapping primitives: any split still has to intersect
+        // both children's worth of area, so it can never beat the leaf cost.
+        let primitives = vec![unit_box_at(0, [0.0, 0.0, 0.0]), unit_box_at(1, [0.1, 0.0, 0.0])];
+        let tree = KDTree::<3>::build_sah(primitives);
+
+        assert_eq!(tree.sah_nodes.len(), 1);
+        let mut leaf_primitives = tree.sah_nodes[0].primitives.clone();
+        leaf_primitives.sort_unstable();
+        assert_eq!(leaf_primitives, vec![0, 1]);
+    }
+
+    #[test]
+    fn nearest_matches_brute_force_search() {
+        let points = vec![
+            [2.0, 3.0],
+            [5.0, 4.0],
+            [9.0, 6.0],
+            [4.0, 7.0],
+            [8.0, 1.0],
+            [7.0, 2.0],
+        ];
+        let kd_tree = KDTree::<2>::generate(points.clone());
+
+        let query = [6.0, 3.0];
+        let nearest_index = kd_tree.nearest(query).expect("tree is non-empty");
+        let nearest_position = kd_tree.kd_nodes[nearest_index].position;
+
+        let brute_force_min_dist = points
+            .iter()
+            .map(|&p| dist_sq(query, p))
+            .fold(f32::INFINITY, f32::min);
+        assert_eq!(dist_sq(query, nearest_position), brute_force_min_dist);
+    }
+
+    #[test]
+    fn nearest_on_empty_tree_returns_none() {
+        let kd_tree = KDTree::<2>::generate(vec![]);
+        assert_eq!(kd_tree.nearest([0.0, 0.0]), None);
+    }
+
+    #[test]
+    fn within_radius_matches_brute_force_search() {
+        let points = vec![
+            [2.0, 3.0],
+            [5.0, 4.0],
+            [9.0, 6.0],
+            [4.0, 7.0],
+            [8.0, 1.0],
+            [7.0, 2.0],
+        ];
+        let kd_tree = KDTree::<2>::generate(points.clone());
+
+        let query = [6.0, 3.0];
+        let radius = 3.5;
+        let mut found: Vec<[f32; 2]> = kd_tree
+            .within_radius(query, radius)
+            .into_iter()
+            .map(|index| kd_tree.kd_nodes[index].position)
+            .collect();
+        found.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut expected: Vec<[f32; 2]> = points
+            .into_iter()
+            .filter(|&p| dist_sq(query, p) <= radius * radius)
+            .collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        assert_eq!(found, expected);
+    }
+
+    fn small_box_cluster(start_index: usize, cluster_origin: f32) -> Vec<BoundedPrimitive> {
+        (0..4)
+            .map(|i| {
+                let offset = cluster_origin + i as f32 * 0.2;
+                BoundedPrimitive {
+                    index: start_index + i,
+                    min: [offset, 0.0, 0.0],
+                    max: [offset + 0.5, 1.0, 1.0],
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn raycast_sah_skips_empty_space_between_distant_clusters() {
+        // Two clusters of primitives far apart on the x axis; the SAH should
+        // split between them so a ray through one cluster never visits the
+        // other cluster's leaf.
+        let mut primitives = small_box_cluster(0, 0.0);
+        primitives.extend(small_box_cluster(4, 100.0));
+        let tree = KDTree::<3>::build_sah(primitives);
+
+        let root = &tree.sah_nodes[0];
+        assert_eq!(root.axis, 0);
+        assert!(root.split > 0.7 && root.split < 100.0);
+
+        let near_cluster_ray = tree.raycast_sah([0.6, 0.5, -10.0], [0.0, 0.0, 1.0]);
+        assert_eq!(near_cluster_ray, vec![0, 1, 2, 3]);
+
+        let far_cluster_ray = tree.raycast_sah([100.6, 0.5, -10.0], [0.0, 0.0, 1.0]);
+        assert_eq!(far_cluster_ray, vec![4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn raycast_sah_visits_straddling_primitives_from_both_sides() {
+        // A primitive spanning the gap between the two clusters straddles
+        // whatever split the clusters induce, so it must show up in both
+        // children's leaves.
+        let mut primitives = small_box_cluster(0, 0.0);
+        primitives.extend(small_box_cluster(4, 100.0));
+        let straddler_index = 8;
+        primitives.push(BoundedPrimitive {
+            index: straddler_index,
+            min: [0.3, 0.0, 0.0],
+            max: [100.3, 1.0, 1.0],
+        });
+        let tree = KDTree::<3>::build_sah(primitives);
+
+        let mut near_hits = tree.raycast_sah([0.6, 0.5, -10.0], [0.0, 0.0, 1.0]);
+        near_hits.sort_unstable();
+        assert_eq!(near_hits, vec![0, 1, 2, 3, straddler_index]);
+
+        let mut far_hits = tree.raycast_sah([100.6, 0.5, -10.0], [0.0, 0.0, 1.0]);
+        far_hits.sort_unstable();
+        assert_eq!(far_hits, vec![4, 5, 6, 7, straddler_index]);
+    }
 }
@@ -1,9 +1,35 @@
 pub trait Brick: Copy + Clone{}
 
-#[derive(Clone, Copy, PartialEq, PartialOrd)]
+/// A leaf covering a 4x4x4 cell as a 64-bit occupancy mask, where bit
+/// `z*16 + y*4 + x` is set if the local voxel at `(x, y, z)` is solid, plus
+/// a material per cell so a brick isn't limited to a single material.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Hash)]
 pub struct Brick64 {
-	pub occupancy: u64
+	pub occupancy: u64,
+	materials: [u32; 64],
 }
 impl Brick for Brick64{
 
+}
+impl Brick64 {
+	pub fn empty() -> Self {
+		Self { occupancy: 0, materials: [0; 64] }
+	}
+	pub fn set(&mut self, bit: usize, material: u32) {
+		self.occupancy |= 1 << bit;
+		self.materials[bit] = material;
+	}
+	/// Clears a cell's occupancy bit and its stored material, so a removed
+	/// cell doesn't leave stale material data behind that would stop two
+	/// otherwise-identical bricks from canonicalizing together.
+	pub fn clear(&mut self, bit: usize) {
+		self.occupancy &= !(1 << bit);
+		self.materials[bit] = 0;
+	}
+	pub fn is_set(&self, bit: usize) -> bool {
+		self.occupancy & (1 << bit) != 0
+	}
+	pub fn material_at(&self, bit: usize) -> u32 {
+		self.materials[bit]
+	}
 }
\ No newline at end of file
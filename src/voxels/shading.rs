@@ -0,0 +1,105 @@
+use glam::Vec3;
+
+use crate::voxels::SparseVoxelOctree;
+
+/// Maximum distance along a directional light's shadow ray at which a voxel
+/// hit still counts as occluding; hits farther away leave the light
+/// unobstructed. Point lights use the distance to the light instead.
+pub const MAX_SHADOW_DISTANCE: f32 = 128.0;
+/// Number of hemisphere samples used by [`ambient_occlusion`].
+pub const AO_SAMPLE_COUNT: usize = 8;
+/// How far an AO ray can travel before it counts as "escaped" rather than
+/// occluded.
+pub const AO_RADIUS: f32 = 1.0;
+/// Offset applied along the surface normal before casting shadow/AO rays,
+/// so they don't immediately re-hit the surface they started from.
+const SHADOW_BIAS: f32 = 1e-3;
+
+/// A light contributing Lambertian shading to [`shade`].
+pub enum Light {
+    Directional { direction: Vec3, color: Vec3 },
+    Point { position: Vec3, color: Vec3 },
+}
+
+/// Accumulates Lambertian lighting at `position`/`normal` from `lights`,
+/// casting a shadow ray per light (offset along the normal to avoid
+/// self-intersection) and skipping any light whose ray hits a voxel before
+/// reaching it.
+pub fn shade(octree: &SparseVoxelOctree, position: Vec3, normal: Vec3, lights: &[Light]) -> Vec3 {
+    let origin = position + normal * SHADOW_BIAS;
+    let mut color = Vec3::ZERO;
+
+    for light in lights {
+        let (light_dir, max_distance, light_color) = match light {
+            Light::Directional { direction, color } => {
+                (-direction.normalize(), MAX_SHADOW_DISTANCE, *color)
+            }
+            Light::Point { position: light_position, color } => {
+                let to_light = *light_position - origin;
+                (to_light.normalize(), to_light.length(), *color)
+            }
+        };
+
+        let n_dot_l = normal.dot(light_dir).max(0.0);
+        if n_dot_l <= 0.0 || in_shadow(octree, origin, light_dir, max_distance) {
+            continue;
+        }
+
+        color += light_color * n_dot_l;
+    }
+
+    color
+}
+
+fn in_shadow(octree: &SparseVoxelOctree, origin: Vec3, direction: Vec3, max_distance: f32) -> bool {
+    match octree.raycast(origin, direction) {
+        Some(hit) => (hit.position - origin).length() < max_distance,
+        None => false,
+    }
+}
+
+/// Estimates ambient occlusion at `position`/`normal` by casting
+/// [`AO_SAMPLE_COUNT`] short rays over the upward hemisphere and returning
+/// the fraction that escape without hitting a voxel within [`AO_RADIUS`].
+pub fn ambient_occlusion(octree: &SparseVoxelOctree, position: Vec3, normal: Vec3) -> f32 {
+    let origin = position + normal * SHADOW_BIAS;
+    let (tangent, bitangent) = orthonormal_basis(normal);
+
+    let mut occluded = 0usize;
+    for i in 0..AO_SAMPLE_COUNT {
+        let sample_dir = hemisphere_sample(normal, tangent, bitangent, i, AO_SAMPLE_COUNT);
+        let hits_nearby = octree
+            .raycast(origin, sample_dir)
+            .map(|hit| (hit.position - origin).length() <= AO_RADIUS)
+            .unwrap_or(false);
+        if hits_nearby {
+            occluded += 1;
+        }
+    }
+
+    1.0 - (occluded as f32 / AO_SAMPLE_COUNT as f32)
+}
+
+fn orthonormal_basis(normal: Vec3) -> (Vec3, Vec3) {
+    let helper = if normal.x.abs() < 0.99 { Vec3::X } else { Vec3::Y };
+    let tangent = helper.cross(normal).normalize();
+    let bitangent = normal.cross(tangent);
+    (tangent, bitangent)
+}
+
+/// Deterministic stratified hemisphere sampling (a golden-angle spiral) so
+/// AO stays stable frame-to-frame instead of flickering under random noise.
+fn hemisphere_sample(normal: Vec3, tangent: Vec3, bitangent: Vec3, index: usize, count: usize) -> Vec3 {
+    let golden_angle = std::f32::consts::PI * (3.0 - 5f32.sqrt());
+    let t = (index as f32 + 0.5) / count as f32;
+    let inclination = (1.0 - t).acos();
+    let azimuth = golden_angle * index as f32;
+
+    let local = Vec3::new(
+        inclination.sin() * azimuth.cos(),
+        inclination.sin() * azimuth.sin(),
+        inclination.cos(),
+    );
+
+    (tangent * local.x + bitangent * local.y + normal * local.z).normalize()
+}